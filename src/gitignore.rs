@@ -0,0 +1,247 @@
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// A single compiled `.gitignore` pattern.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct Pattern {
+    /// The glob, with a leading `!` and any anchoring/trailing slashes removed.
+    glob: String,
+    /// `true` if the pattern was prefixed with `!` and thus re-includes a path.
+    negated: bool,
+    /// `true` if the pattern ended with `/` and therefore matches directories only.
+    dir_only: bool,
+    /// `true` if the pattern is anchored to the directory holding the ignore file
+    /// (a `/` at the start or in the middle), rather than matched against the
+    /// file name at any level.
+    anchored: bool,
+}
+
+impl Pattern {
+    fn parse(line: &str) -> Option<Pattern> {
+        let line: &str = line.trim_end();
+        if line.is_empty() || line.starts_with('#') {
+            return None;
+        }
+        let (negated, rest): (bool, &str) = match line.strip_prefix('!') {
+            Some(rest) => (true, rest),
+            None => (false, line),
+        };
+        let leading_slash: bool = rest.starts_with('/');
+        let dir_only: bool = rest.ends_with('/');
+        let inner: &str = rest.trim_matches('/');
+        if inner.is_empty() {
+            return None;
+        }
+        let anchored: bool = leading_slash || inner.contains('/');
+        Some(Pattern {
+            glob: inner.to_string(),
+            negated,
+            dir_only,
+            anchored,
+        })
+    }
+
+    fn matches(&self, rel: &str, is_dir: bool) -> bool {
+        if self.dir_only && !is_dir {
+            return false;
+        }
+        if self.anchored {
+            let pat: Vec<&str> = self.glob.split('/').collect();
+            let text: Vec<&str> = rel.split('/').collect();
+            match_segments(&pat, &text)
+        } else {
+            let name: &str = rel.rsplit('/').next().unwrap_or(rel);
+            wildcard(self.glob.as_bytes(), name.as_bytes())
+        }
+    }
+}
+
+/// The compiled contents of a single `.gitignore` file, together with the
+/// directory it was found in. Paths are matched relative to that directory.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct Gitignore {
+    base: PathBuf,
+    patterns: Vec<Pattern>,
+}
+
+impl Gitignore {
+    /// Parse the contents of an ignore file located in `base`. Returns `None` if
+    /// the file contained no usable patterns.
+    pub(crate) fn parse(base: &Path, contents: &str) -> Option<Gitignore> {
+        let patterns: Vec<Pattern> = contents.lines().filter_map(Pattern::parse).collect();
+        if patterns.is_empty() {
+            None
+        } else {
+            Some(Gitignore {
+                base: base.to_path_buf(),
+                patterns,
+            })
+        }
+    }
+
+    /// `Some(true)` if this file ignores `path`, `Some(false)` if it explicitly
+    /// re-includes it via a `!` pattern, and `None` if it has no opinion. The
+    /// last matching pattern wins.
+    fn matched(&self, path: &Path, is_dir: bool) -> Option<bool> {
+        let rel: &Path = path.strip_prefix(&self.base).ok()?;
+        let rel: &str = rel.to_str()?;
+        let mut decision: Option<bool> = None;
+        for pattern in &self.patterns {
+            if pattern.matches(rel, is_dir) {
+                decision = Some(!pattern.negated);
+            }
+        }
+        decision
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+struct Node {
+    parent: IgnoreStack,
+    matcher: Gitignore,
+}
+
+/// An immutable stack of [`Gitignore`] matchers, ordered from the origin down to
+/// the directory currently being read. Because each stack is shared by reference
+/// counting and never mutated, a `.gitignore` pushed while descending into a
+/// subdirectory only affects that subtree — the parent keeps its own stack.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub(crate) struct IgnoreStack(Option<Arc<Node>>);
+
+impl IgnoreStack {
+    /// Return a new stack with `matcher` pushed on top of this one.
+    pub(crate) fn push(&self, matcher: Gitignore) -> IgnoreStack {
+        IgnoreStack(Some(Arc::new(Node {
+            parent: self.clone(),
+            matcher,
+        })))
+    }
+
+    /// `true` if `path` is ignored by any matcher in the stack. Matchers closer
+    /// to `path` (pushed later) override the decision of shallower ones, which in
+    /// turn lets a `!` negation re-include a path excluded higher up.
+    pub(crate) fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        self.decision(path, is_dir).unwrap_or(false)
+    }
+
+    fn decision(&self, path: &Path, is_dir: bool) -> Option<bool> {
+        let node: &Arc<Node> = self.0.as_ref()?;
+        let parent: Option<bool> = node.parent.decision(path, is_dir);
+        node.matcher.matched(path, is_dir).or(parent)
+    }
+}
+
+/// A standalone glob matched against a whole path, used for the Walker's
+/// `exclude_globs`/`allow_globs` lists. Unlike [`Gitignore`] patterns these
+/// carry no base directory and no `.gitignore` semantics — a `**` segment
+/// matches zero or more path components, `*` and `?` match within a single
+/// component.
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub(crate) struct Glob {
+    segments: Vec<String>,
+}
+
+impl Glob {
+    pub(crate) fn new(pattern: &str) -> Glob {
+        Glob {
+            segments: pattern.split('/').map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// `true` if `path` matches this glob.
+    pub(crate) fn matches(&self, path: &Path) -> bool {
+        let path: &str = match path.to_str() {
+            Some(path) => path,
+            None => return false,
+        };
+        let pattern: Vec<&str> = self.segments.iter().map(String::as_str).collect();
+        let text: Vec<&str> = path.split('/').collect();
+        match_segments(&pattern, &text)
+    }
+}
+
+/// Match a single path component against a glob containing `*` and `?`, where
+/// neither wildcard crosses a `/` boundary.
+fn wildcard(pattern: &[u8], text: &[u8]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(b'*') => {
+            wildcard(&pattern[1..], text) || (!text.is_empty() && wildcard(pattern, &text[1..]))
+        }
+        Some(b'?') => !text.is_empty() && wildcard(&pattern[1..], &text[1..]),
+        Some(&c) => !text.is_empty() && text[0] == c && wildcard(&pattern[1..], &text[1..]),
+    }
+}
+
+/// Match an anchored, `/`-separated glob against a path, where a `**` segment
+/// matches zero or more path components.
+fn match_segments(pattern: &[&str], text: &[&str]) -> bool {
+    match pattern.first() {
+        None => text.is_empty(),
+        Some(&"**") => {
+            match_segments(&pattern[1..], text)
+                || (!text.is_empty() && match_segments(pattern, &text[1..]))
+        }
+        Some(segment) => {
+            !text.is_empty()
+                && wildcard(segment.as_bytes(), text[0].as_bytes())
+                && match_segments(&pattern[1..], &text[1..])
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Gitignore;
+    use std::path::{Path, PathBuf};
+
+    fn stack(base: &str, contents: &str) -> super::IgnoreStack {
+        let matcher = Gitignore::parse(Path::new(base), contents).unwrap();
+        super::IgnoreStack::default().push(matcher)
+    }
+
+    #[test]
+    fn test_basename_pattern_matches_at_any_depth() {
+        let stack = stack("root", "*.log\n");
+        assert!(stack.is_ignored(&PathBuf::from("root/a.log"), false));
+        assert!(stack.is_ignored(&PathBuf::from("root/sub/b.log"), false));
+        assert!(!stack.is_ignored(&PathBuf::from("root/a.txt"), false));
+    }
+
+    #[test]
+    fn test_anchored_pattern() {
+        let stack = stack("root", "/build\n");
+        assert!(stack.is_ignored(&PathBuf::from("root/build"), true));
+        assert!(!stack.is_ignored(&PathBuf::from("root/sub/build"), true));
+    }
+
+    #[test]
+    fn test_dir_only_pattern_ignores_files() {
+        let stack = stack("root", "cache/\n");
+        assert!(stack.is_ignored(&PathBuf::from("root/cache"), true));
+        assert!(!stack.is_ignored(&PathBuf::from("root/cache"), false));
+    }
+
+    #[test]
+    fn test_negation_reincludes() {
+        let stack = stack("root", "*.log\n!keep.log\n");
+        assert!(stack.is_ignored(&PathBuf::from("root/a.log"), false));
+        assert!(!stack.is_ignored(&PathBuf::from("root/keep.log"), false));
+    }
+
+    #[test]
+    fn test_glob_matches_whole_path() {
+        use super::Glob;
+        let glob = Glob::new("**/target/**");
+        assert!(glob.matches(&PathBuf::from("a/target/b/c")));
+        assert!(glob.matches(&PathBuf::from("a/target")));
+        assert!(!glob.matches(&PathBuf::from("a/src/b")));
+    }
+
+    #[test]
+    fn test_double_star() {
+        let stack = stack("root", "**/target\n");
+        assert!(stack.is_ignored(&PathBuf::from("root/target"), true));
+        assert!(stack.is_ignored(&PathBuf::from("root/a/b/target"), true));
+    }
+}
@@ -1,21 +1,277 @@
 use std::collections::VecDeque;
 use std::fmt::Formatter;
-use std::fs::ReadDir;
+use std::fs::{FileType, Metadata, ReadDir};
+use std::hash::{Hash, Hasher};
 use std::io::ErrorKind;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::cell::OnceCell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering as AtomicOrdering};
+use std::sync::mpsc::Sender;
+use std::sync::{Arc, Condvar, Mutex};
 use std::{cmp::Ordering, fs::DirEntry};
 
+use crate::gitignore::{Gitignore, Glob, IgnoreStack};
+
 mod fs;
+mod gitignore;
 
+/// A snapshot of a walk's progress, emitted on the channel registered with
+/// [`Walker::with_progress`] every time a directory has been read, or has failed
+/// to be read.
 #[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct WalkProgress {
+    /// The number of directories read successfully so far, including the one
+    /// just reported if it was read successfully.
+    pub directories_visited: usize,
+    /// The number of files discovered so far.
+    pub files_discovered: usize,
+    /// The directory that was just read, or that just failed to be read.
+    pub current_directory: PathBuf,
+    /// The number of directories that could not be read so far.
+    pub errors: usize,
+}
+
+/// Instruction returned by a [`Walker::walk_parallel`] callback, controlling how
+/// the traversal proceeds after an entry has been handled.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum WalkState {
+    /// Keep walking; descend into the entry if it is a directory.
+    Continue,
+    /// Do not descend into this directory. Has no effect for files.
+    SkipDir,
+    /// Stop the entire walk as soon as possible.
+    Quit,
+}
+
+/// A single entry produced while walking a directory tree.
+///
+/// A `FileEntry` carries the information the [`Walker`] already obtained while
+/// descending — the [`PathBuf`], the [`FileType`] fetched when the entry was
+/// validated, and the traversal depth relative to the origin — so that callers
+/// do not have to `stat` the path a second time to learn its type or depth. The
+/// [`Metadata`] is fetched lazily the first time [`FileEntry::metadata`] is
+/// called and cached for any later calls.
+#[derive(Debug)]
+pub struct FileEntry {
+    path: PathBuf,
+    file_type: FileType,
+    depth: u32,
+    metadata: OnceCell<Metadata>,
+}
+
+impl FileEntry {
+    fn new(path: PathBuf, file_type: FileType, depth: u32) -> FileEntry {
+        FileEntry {
+            path,
+            file_type,
+            depth,
+            metadata: OnceCell::new(),
+        }
+    }
+
+    /// The path of this entry.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Consume the entry and return its owned `PathBuf`.
+    pub fn into_path(self) -> PathBuf {
+        self.path
+    }
+
+    /// The [`FileType`] obtained while the entry was discovered, without an
+    /// additional syscall.
+    pub fn file_type(&self) -> FileType {
+        self.file_type
+    }
+
+    /// The depth of this entry relative to the origin of the walk. The origin
+    /// itself has depth `0`, its immediate children depth `1`, and so on.
+    pub fn depth(&self) -> u32 {
+        self.depth
+    }
+
+    /// `true` if this entry is a regular file.
+    pub fn is_file(&self) -> bool {
+        self.file_type.is_file()
+    }
+
+    /// `true` if this entry is a directory.
+    pub fn is_dir(&self) -> bool {
+        self.file_type.is_dir()
+    }
+
+    /// `true` if this entry is a symbolic link.
+    pub fn is_symlink(&self) -> bool {
+        self.file_type.is_symlink()
+    }
+
+    /// Return the [`Metadata`] for this entry, fetching it on the first call and
+    /// returning the cached value on every call after that.
+    pub fn metadata(&self) -> Result<&Metadata, std::io::Error> {
+        if let Some(metadata) = self.metadata.get() {
+            return Ok(metadata);
+        }
+        let metadata: Metadata = std::fs::metadata(&self.path)?;
+        Ok(self.metadata.get_or_init(|| metadata))
+    }
+}
+
+impl Clone for FileEntry {
+    fn clone(&self) -> Self {
+        FileEntry {
+            path: self.path.clone(),
+            file_type: self.file_type,
+            depth: self.depth,
+            metadata: OnceCell::new(),
+        }
+    }
+}
+
+impl PartialEq for FileEntry {
+    fn eq(&self, other: &Self) -> bool {
+        self.path == other.path && self.depth == other.depth
+    }
+}
+
+impl Eq for FileEntry {}
+
+impl Hash for FileEntry {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.path.hash(state);
+        self.depth.hash(state);
+    }
+}
+
+/// Predicate applied to a directory before the Walker descends into it.
+type EntryFilter = Arc<dyn Fn(&FileEntry) -> bool + Send + Sync>;
+/// Comparator used to order the children of each directory before they are emitted.
+type EntrySort = Arc<dyn Fn(&FileEntry, &FileEntry) -> Ordering + Send + Sync>;
+/// A path paired with the [`FileType`] learned while reading its parent directory.
+type RawEntry = (PathBuf, FileType);
+
 pub struct Walker {
-    files: VecDeque<PathBuf>,
-    dirs: VecDeque<PathBuf>,
+    files: VecDeque<FileEntry>,
+    dirs: VecDeque<(PathBuf, u32, IgnoreStack)>,
     ignore: Vec<PathBuf>,
+    ignore_files: Vec<PathBuf>,
+    use_ignore_files: bool,
+    extensions: Vec<String>,
+    exclude_globs: Vec<Glob>,
+    allow_globs: Vec<Glob>,
     origin: PathBuf,
-    origin_depth: usize,
     max_depth: Option<u32>,
+    min_depth: Option<u32>,
     follow_symlinks: bool,
+    yield_dirs: bool,
+    filter_entry: Option<EntryFilter>,
+    sort_by: Option<EntrySort>,
+    progress: Option<Sender<WalkProgress>>,
+    cancel: Option<Arc<AtomicBool>>,
+    dirs_visited: usize,
+    files_discovered: usize,
+    errors: usize,
+}
+
+impl Clone for Walker {
+    fn clone(&self) -> Self {
+        Walker {
+            files: self.files.clone(),
+            dirs: self.dirs.clone(),
+            ignore: self.ignore.clone(),
+            ignore_files: self.ignore_files.clone(),
+            use_ignore_files: self.use_ignore_files,
+            extensions: self.extensions.clone(),
+            exclude_globs: self.exclude_globs.clone(),
+            allow_globs: self.allow_globs.clone(),
+            origin: self.origin.clone(),
+            max_depth: self.max_depth,
+            min_depth: self.min_depth,
+            follow_symlinks: self.follow_symlinks,
+            yield_dirs: self.yield_dirs,
+            filter_entry: self.filter_entry.clone(),
+            sort_by: self.sort_by.clone(),
+            progress: self.progress.clone(),
+            cancel: self.cancel.clone(),
+            dirs_visited: self.dirs_visited,
+            files_discovered: self.files_discovered,
+            errors: self.errors,
+        }
+    }
+}
+
+/// Equality and hashing cover the configuration and traversal state but not the
+/// `filter_entry` predicate or `sort_by` comparator, which are opaque closures,
+/// nor the progress sender or cancellation flag.
+impl PartialEq for Walker {
+    fn eq(&self, other: &Self) -> bool {
+        self.files == other.files
+            && self.dirs == other.dirs
+            && self.ignore == other.ignore
+            && self.ignore_files == other.ignore_files
+            && self.use_ignore_files == other.use_ignore_files
+            && self.extensions == other.extensions
+            && self.exclude_globs == other.exclude_globs
+            && self.allow_globs == other.allow_globs
+            && self.origin == other.origin
+            && self.max_depth == other.max_depth
+            && self.min_depth == other.min_depth
+            && self.follow_symlinks == other.follow_symlinks
+            && self.yield_dirs == other.yield_dirs
+            && self.dirs_visited == other.dirs_visited
+            && self.files_discovered == other.files_discovered
+            && self.errors == other.errors
+    }
+}
+
+impl Eq for Walker {}
+
+impl Hash for Walker {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.files.hash(state);
+        self.dirs.hash(state);
+        self.ignore.hash(state);
+        self.ignore_files.hash(state);
+        self.use_ignore_files.hash(state);
+        self.extensions.hash(state);
+        self.exclude_globs.hash(state);
+        self.allow_globs.hash(state);
+        self.origin.hash(state);
+        self.max_depth.hash(state);
+        self.min_depth.hash(state);
+        self.follow_symlinks.hash(state);
+        self.yield_dirs.hash(state);
+        self.dirs_visited.hash(state);
+        self.files_discovered.hash(state);
+        self.errors.hash(state);
+    }
+}
+
+impl std::fmt::Debug for Walker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Walker")
+            .field("files", &self.files)
+            .field("dirs", &self.dirs)
+            .field("ignore", &self.ignore)
+            .field("ignore_files", &self.ignore_files)
+            .field("use_ignore_files", &self.use_ignore_files)
+            .field("extensions", &self.extensions)
+            .field("exclude_globs", &self.exclude_globs)
+            .field("allow_globs", &self.allow_globs)
+            .field("origin", &self.origin)
+            .field("max_depth", &self.max_depth)
+            .field("min_depth", &self.min_depth)
+            .field("follow_symlinks", &self.follow_symlinks)
+            .field("yield_dirs", &self.yield_dirs)
+            .field("filter_entry", &self.filter_entry.as_ref().map(|_| "<fn>"))
+            .field("sort_by", &self.sort_by.as_ref().map(|_| "<fn>"))
+            .field("progress", &self.progress.as_ref().map(|_| "<sender>"))
+            .field("cancel", &self.cancel)
+            .field("dirs_visited", &self.dirs_visited)
+            .field("files_discovered", &self.files_discovered)
+            .field("errors", &self.errors)
+            .finish()
+    }
 }
 
 impl Walker {
@@ -23,7 +279,7 @@ impl Walker {
     /// This Walker will not follow symlinks and will not have any limitation
     /// in recursion depth for directories.
     pub fn new() -> Result<Walker, std::io::Error> {
-        Walker::from(&PathBuf::from("."))
+        Walker::from(PathBuf::from("."))
     }
 
     /// Create a new Walker starting from the current directory (path `.`), with the
@@ -33,7 +289,7 @@ impl Walker {
     /// This Walker will not follow symlinks and will not have any limitation
     /// in recursion depth for directories.
     pub fn with_capacity(capacity: usize) -> Result<Walker, std::io::Error> {
-        Walker::from_with_capacity(&PathBuf::from("."), capacity)
+        Walker::from_with_capacity(PathBuf::from("."), capacity)
     }
 
     /// Create a new Walker for the given path with an initial capacity of 16.
@@ -101,17 +357,30 @@ impl Walker {
             return Err(err);
         }
         let mut dirs = VecDeque::with_capacity(capacity);
-        dirs.push_back(path.clone());
+        dirs.push_back((path.clone(), 0, IgnoreStack::default()));
         let files = VecDeque::with_capacity(capacity);
 
         let walker = Walker {
             files,
             dirs,
             ignore: vec![],
+            ignore_files: vec![],
+            use_ignore_files: false,
+            extensions: vec![],
+            exclude_globs: vec![],
+            allow_globs: vec![],
             origin: path.to_path_buf(),
-            origin_depth: components(&path),
             max_depth: None,
+            min_depth: None,
             follow_symlinks: false,
+            yield_dirs: false,
+            filter_entry: None,
+            sort_by: None,
+            progress: None,
+            cancel: None,
+            dirs_visited: 0,
+            files_discovered: 0,
+            errors: 0,
         };
         Ok(walker)
     }
@@ -123,6 +392,85 @@ impl Walker {
         self
     }
 
+    /// Set the minimum depth, relative to the origin, at which entries are
+    /// emitted. Entries shallower than `depth` are still traversed through but
+    /// not yielded. This is the companion to [`Walker::max_depth`].
+    pub fn min_depth(mut self, depth: u32) -> Walker {
+        self.min_depth = Some(depth);
+        self
+    }
+
+    /// Emit directories as items in addition to files. By default the Walker
+    /// yields files only.
+    pub fn yield_dirs(mut self) -> Walker {
+        self.yield_dirs = true;
+        self
+    }
+
+    /// Set a predicate evaluated on each directory before the Walker descends
+    /// into it. When it returns `false` the directory is neither read nor
+    /// emitted, so an entire subtree can be skipped without the cost of reading
+    /// it.
+    pub fn filter_entry<F>(mut self, pred: F) -> Walker
+    where
+        F: Fn(&FileEntry) -> bool + Send + Sync + 'static,
+    {
+        self.filter_entry = Some(Arc::new(pred));
+        self
+    }
+
+    /// Order the children of each directory with `cmp` before they are emitted,
+    /// making the iteration deterministic regardless of the order `read_dir`
+    /// happens to return entries in.
+    pub fn sort_by<F>(mut self, cmp: F) -> Walker
+    where
+        F: Fn(&FileEntry, &FileEntry) -> Ordering + Send + Sync + 'static,
+    {
+        self.sort_by = Some(Arc::new(cmp));
+        self
+    }
+
+    /// Order the children of each directory by file name. A convenience wrapper
+    /// around [`Walker::sort_by`].
+    pub fn sort_by_file_name(self) -> Walker {
+        self.sort_by(|a, b| a.path().file_name().cmp(&b.path().file_name()))
+    }
+
+    /// Register a channel on which a [`WalkProgress`] snapshot is sent each time
+    /// a directory has been read or has failed to be read, letting an interactive front end monitor a
+    /// long-running walk. Send errors are ignored so that a disconnected
+    /// receiver never aborts the walk.
+    pub fn with_progress(mut self, tx: Sender<WalkProgress>) -> Walker {
+        self.progress = Some(tx);
+        self
+    }
+
+    /// Register a cancellation flag that is checked while iterating (and by
+    /// [`Walker::walk_parallel`]). Setting it to `true` stops the walk cleanly
+    /// as soon as the flag is observed.
+    pub fn with_cancel(mut self, cancel: Arc<AtomicBool>) -> Walker {
+        self.cancel = Some(cancel);
+        self
+    }
+
+    fn report(&self, current: &Path) {
+        if let Some(tx) = &self.progress {
+            let _ = tx.send(WalkProgress {
+                directories_visited: self.dirs_visited,
+                files_discovered: self.files_discovered,
+                current_directory: current.to_path_buf(),
+                errors: self.errors,
+            });
+        }
+    }
+
+    fn is_cancelled(&self) -> bool {
+        match &self.cancel {
+            Some(flag) => flag.load(AtomicOrdering::SeqCst),
+            None => false,
+        }
+    }
+
     /// Enable following of symlinks on the current Walker when traversing through files.
     /// Once this option has been enabled for a Walker, it cannot be disabled again.
     pub fn follow_symlinks(mut self) -> Walker {
@@ -139,6 +487,292 @@ impl Walker {
         self
     }
 
+    /// Parse `.gitignore` files encountered while descending and prune matching
+    /// files and directories before they enter the queues. A `.gitignore` in a
+    /// subdirectory only affects that subtree; the standard semantics are
+    /// honoured — glob patterns, `!` negation, leading `/` anchoring and trailing
+    /// `/` matching directories only.
+    pub fn use_ignore_files(mut self) -> Walker {
+        self.use_ignore_files = true;
+        self
+    }
+
+    /// Register an additional ignore file, formatted like `.gitignore`, whose
+    /// patterns apply to the whole walk. Patterns are matched relative to the
+    /// directory containing the file.
+    pub fn add_ignore_file<T: Into<PathBuf>>(mut self, path: T) -> Walker {
+        self.ignore_files.push(path.into());
+        self
+    }
+
+    /// Restrict the emitted files to those whose final `.ext` matches one of the
+    /// given extensions. Matching is case-insensitive and the extensions are
+    /// given without the leading dot (e.g. `["rs", "toml"]`). Directories are
+    /// unaffected.
+    pub fn with_extensions(mut self, extensions: &[&str]) -> Walker {
+        self.extensions = extensions
+            .iter()
+            .map(|e| e.to_ascii_lowercase())
+            .collect();
+        self
+    }
+
+    /// Prune files and directories whose full path matches one of the given
+    /// glob patterns. Excluded directories are never read, so an expensive
+    /// subtree such as `**/target/**` can be skipped entirely.
+    pub fn exclude_globs(mut self, globs: &[&str]) -> Walker {
+        self.exclude_globs = globs.iter().map(|g| Glob::new(g)).collect();
+        self
+    }
+
+    /// Emit only files whose full path matches one of the given glob patterns.
+    /// Unlike [`Walker::exclude_globs`] this filters emitted files only and does
+    /// not affect which directories are descended into.
+    pub fn allow_globs(mut self, globs: &[&str]) -> Walker {
+        self.allow_globs = globs.iter().map(|g| Glob::new(g)).collect();
+        self
+    }
+
+    fn is_excluded(&self, path: &Path) -> bool {
+        self.exclude_globs.iter().any(|g| g.matches(path))
+    }
+
+    fn has_allowed_extension(&self, path: &Path) -> bool {
+        if self.extensions.is_empty() {
+            return true;
+        }
+        match path.extension().and_then(|e| e.to_str()) {
+            Some(ext) => self.extensions.contains(&ext.to_ascii_lowercase()),
+            None => false,
+        }
+    }
+
+    fn is_allowed_glob(&self, path: &Path) -> bool {
+        self.allow_globs.is_empty() || self.allow_globs.iter().any(|g| g.matches(path))
+    }
+
+    /// Build the base ignore stack from the explicitly registered ignore files.
+    /// This is the stack in effect at the origin, before any in-tree
+    /// `.gitignore` files are layered on top.
+    fn base_stack(&self) -> IgnoreStack {
+        let mut stack: IgnoreStack = IgnoreStack::default();
+        for file in &self.ignore_files {
+            let base: &Path = file.parent().unwrap_or_else(|| Path::new("."));
+            if let Ok(contents) = std::fs::read_to_string(file) {
+                if let Some(matcher) = Gitignore::parse(base, &contents) {
+                    stack = stack.push(matcher);
+                }
+            }
+        }
+        stack
+    }
+
+    /// Layer the `.gitignore` found in `dir` (if any, and if enabled) on top of
+    /// `stack`, returning the stack that applies to the children of `dir`.
+    fn extend_stack(&self, dir: &Path, stack: IgnoreStack) -> IgnoreStack {
+        if !self.use_ignore_files {
+            return stack;
+        }
+        let ignore_file: PathBuf = dir.join(".gitignore");
+        match std::fs::read_to_string(&ignore_file) {
+            Ok(contents) => match Gitignore::parse(dir, &contents) {
+                Some(matcher) => stack.push(matcher),
+                None => stack,
+            },
+            Err(_) => stack,
+        }
+    }
+
+    /// Traverse the tree using a pool of `n_threads` worker threads, invoking
+    /// `f` for every entry as it is discovered. This trades the deterministic,
+    /// single-threaded [`Iterator`] order for throughput and is the fastest way
+    /// to walk large trees.
+    ///
+    /// The callback is handed each file and each directory as an owned
+    /// [`FileEntry`] and returns a [`WalkState`] that controls the traversal:
+    /// [`WalkState::Continue`] keeps descending, [`WalkState::SkipDir`] prunes
+    /// the directory it was returned for, and [`WalkState::Quit`] stops the walk
+    /// altogether. The full configuration of the Walker — `max_depth`,
+    /// `min_depth`, `follow_symlinks`, the ignore list, `.gitignore` handling,
+    /// `exclude_globs`/`allow_globs`, `with_extensions` and the `filter_entry`
+    /// predicate — is honoured exactly as for the sequential iterator.
+    pub fn walk_parallel<F>(self, n_threads: usize, f: F)
+    where
+        F: Fn(FileEntry) -> WalkState + Send + Sync,
+    {
+        let n_threads: usize = n_threads.max(1);
+        // The same configuration `push()` applies in the sequential iterator is
+        // pulled out here so the two traversal modes prune identically.
+        let follow_symlinks: bool = self.follow_symlinks;
+        let max_depth: Option<u32> = self.max_depth;
+        let min_depth: Option<u32> = self.min_depth;
+        let use_ignore_files: bool = self.use_ignore_files;
+        let ignore: Vec<PathBuf> = self.ignore.clone();
+        let exclude_globs: Vec<Glob> = self.exclude_globs.clone();
+        let allow_globs: Vec<Glob> = self.allow_globs.clone();
+        let extensions: Vec<String> = self.extensions.clone();
+        let filter_entry: Option<EntryFilter> = self.filter_entry.clone();
+        let cancel: Option<Arc<AtomicBool>> = self.cancel.clone();
+        let base_stack: IgnoreStack = self.base_stack();
+        let origin: PathBuf = self.origin.clone();
+
+        let queue: Mutex<VecDeque<(PathBuf, u32, IgnoreStack)>> = Mutex::new(VecDeque::new());
+        let idle: Condvar = Condvar::new();
+        let in_flight = AtomicUsize::new(1);
+        let quit = AtomicBool::new(false);
+
+        queue
+            .lock()
+            .unwrap()
+            .push_back((origin.clone(), 0, IgnoreStack::default()));
+
+        std::thread::scope(|scope| {
+            for _ in 0..n_threads {
+                let queue: &Mutex<VecDeque<(PathBuf, u32, IgnoreStack)>> = &queue;
+                let idle: &Condvar = &idle;
+                let in_flight: &AtomicUsize = &in_flight;
+                let quit: &AtomicBool = &quit;
+                let ignore: &[PathBuf] = &ignore;
+                let exclude_globs: &[Glob] = &exclude_globs;
+                let allow_globs: &[Glob] = &allow_globs;
+                let extensions: &[String] = &extensions;
+                let filter_entry: Option<&EntryFilter> = filter_entry.as_ref();
+                let base_stack: &IgnoreStack = &base_stack;
+                let origin: &Path = &origin;
+                let cancel: Option<&Arc<AtomicBool>> = cancel.as_ref();
+                let f: &F = &f;
+                let cancelled = move || cancel.is_some_and(|c| c.load(AtomicOrdering::SeqCst));
+                scope.spawn(move || {
+                    // Inline equivalents of the `push()` filter helpers, reading
+                    // the configuration extracted above.
+                    let excluded = |p: &Path| exclude_globs.iter().any(|g| g.matches(p));
+                    let allowed_ext = |p: &Path| {
+                        extensions.is_empty()
+                            || match p.extension().and_then(|e| e.to_str()) {
+                                Some(ext) => extensions.contains(&ext.to_ascii_lowercase()),
+                                None => false,
+                            }
+                    };
+                    let allowed_glob =
+                        |p: &Path| allow_globs.is_empty() || allow_globs.iter().any(|g| g.matches(p));
+                    let keep = |d: &FileEntry| keep_entry(filter_entry, d);
+                    let at_min = |depth: u32| at_min_depth(min_depth, depth);
+                    let extend = |dir: &Path, stack: IgnoreStack| -> IgnoreStack {
+                        if !use_ignore_files {
+                            return stack;
+                        }
+                        let ignore_file: PathBuf = dir.join(".gitignore");
+                        match std::fs::read_to_string(&ignore_file) {
+                            Ok(contents) => match Gitignore::parse(dir, &contents) {
+                                Some(matcher) => stack.push(matcher),
+                                None => stack,
+                            },
+                            Err(_) => stack,
+                        }
+                    };
+                    loop {
+                    let (dir, depth, incoming): (PathBuf, u32, IgnoreStack) = {
+                        let mut pending = queue.lock().unwrap();
+                        loop {
+                            if quit.load(AtomicOrdering::SeqCst) || cancelled() {
+                                return;
+                            }
+                            if let Some(item) = pending.pop_front() {
+                                break item;
+                            }
+                            if in_flight.load(AtomicOrdering::SeqCst) == 0 {
+                                idle.notify_all();
+                                return;
+                            }
+                            pending = idle.wait(pending).unwrap();
+                        }
+                    };
+
+                    // The ignore stack that governs the children of `dir`, mirroring
+                    // the `push()` logic: the origin layers its `.gitignore` on the
+                    // registered base, every other directory on the stack it inherited.
+                    let stack: IgnoreStack = if dir == origin {
+                        extend(&dir, base_stack.clone())
+                    } else {
+                        extend(&dir, incoming)
+                    };
+
+                    match read_entries(&dir, depth + 1, follow_symlinks) {
+                        Ok((files, dirs)) => {
+                            let dirs: Vec<FileEntry> = dirs
+                                .into_iter()
+                                .filter(|d| !stack.is_ignored(d.path(), true))
+                                .filter(|d| !ignore.iter().any(|b| b.as_path() == d.path()))
+                                .filter(|d| !excluded(d.path()))
+                                .filter(|d| keep(d))
+                                .collect();
+                            let files: Vec<FileEntry> = files
+                                .into_iter()
+                                .filter(|f| !stack.is_ignored(f.path(), false))
+                                .filter(|f| !excluded(f.path()))
+                                .filter(|f| allowed_ext(f.path()))
+                                .filter(|f| allowed_glob(f.path()))
+                                .collect();
+
+                            for file in files {
+                                if !at_min(file.depth()) {
+                                    continue;
+                                }
+                                if f(file) == WalkState::Quit {
+                                    quit.store(true, AtomicOrdering::SeqCst);
+                                    idle.notify_all();
+                                    break;
+                                }
+                            }
+                            if !quit.load(AtomicOrdering::SeqCst) {
+                                let descend: bool = !at_depth_limit(max_depth, depth);
+                                for entry in dirs {
+                                    let path: PathBuf = entry.path().to_path_buf();
+                                    let sub_depth: u32 = entry.depth();
+                                    // Directories shallower than `min_depth` are not
+                                    // emitted but are still descended into.
+                                    let state: WalkState = if at_min(sub_depth) {
+                                        f(entry)
+                                    } else {
+                                        WalkState::Continue
+                                    };
+                                    match state {
+                                        WalkState::Continue if descend => {
+                                            in_flight.fetch_add(1, AtomicOrdering::SeqCst);
+                                            queue
+                                                .lock()
+                                                .unwrap()
+                                                .push_back((path, sub_depth, stack.clone()));
+                                            idle.notify_one();
+                                        }
+                                        WalkState::Continue | WalkState::SkipDir => {}
+                                        WalkState::Quit => {
+                                            quit.store(true, AtomicOrdering::SeqCst);
+                                            idle.notify_all();
+                                            break;
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                        Err(e) => log::warn!("{}: {:?}", e, dir),
+                    }
+
+                    in_flight.fetch_sub(1, AtomicOrdering::SeqCst);
+                    idle.notify_all();
+                    }
+                });
+            }
+        });
+    }
+
+    /// Adapt this Walker into an iterator of plain [`PathBuf`]s, discarding the
+    /// richer [`FileEntry`] information. This is a convenience for callers that
+    /// were written against the previous `Item = PathBuf` iterator.
+    pub fn paths(self) -> impl Iterator<Item = PathBuf> {
+        self.map(FileEntry::into_path)
+    }
+
     /// Reset a Walker to its original state, starting over with iterating from the _origin_
     /// `PathBuf`. Changes made to the Walker after it was created with `max_depth()` and
     /// `follow_symlinks()` will not be reset.
@@ -149,72 +783,143 @@ impl Walker {
     pub fn reset(&mut self) -> &mut Walker {
         self.files.clear();
         self.dirs.clear();
-        self.dirs.push_back(self.origin.to_path_buf());
+        self.dirs
+            .push_back((self.origin.to_path_buf(), 0, IgnoreStack::default()));
+        self.dirs_visited = 0;
+        self.files_discovered = 0;
+        self.errors = 0;
         self
     }
 
-    fn load(&self, path: &PathBuf) -> Result<(Vec<PathBuf>, Vec<PathBuf>), std::io::Error> {
-        let path: ReadDir = read_dirs(&path)?;
-        let (files, dirs) = path
-            .filter_map(|p| p.ok())
-            .filter(|d: &DirEntry| is_valid_target(d, self.follow_symlinks))
-            .map(|d: DirEntry| d.path())
-            .partition(|p| p.is_file());
-
-        Ok((files, dirs))
+    fn load(
+        &self,
+        path: &PathBuf,
+        depth: u32,
+    ) -> Result<(Vec<FileEntry>, Vec<FileEntry>), std::io::Error> {
+        read_entries(path, depth, self.follow_symlinks)
     }
 
-    fn push(&mut self, path: &PathBuf) {
-        match self.load(path) {
+    fn push(&mut self, path: &PathBuf, depth: u32, incoming: IgnoreStack) {
+        let stack: IgnoreStack = if path == &self.origin {
+            self.extend_stack(path, self.base_stack())
+        } else {
+            self.extend_stack(path, incoming)
+        };
+        match self.load(path, depth + 1) {
             Ok((files, dirs)) => {
-                self.files.extend(files);
-                if !self.at_max_depth(path) {
-                    let dirs: Vec<PathBuf> = filter_boundaries(dirs, &self.ignore);
-                    self.dirs.extend(dirs);
+                // Directories that survive the ignore rules, file-system
+                // boundaries and the optional `filter_entry` predicate. These are
+                // both descended into and, when `yield_dirs` is set, emitted.
+                let dirs: Vec<FileEntry> = dirs
+                    .into_iter()
+                    .filter(|d| !stack.is_ignored(d.path(), true))
+                    .filter(|d| !self.ignore.iter().any(|b| b.as_path() == d.path()))
+                    .filter(|d| !self.is_excluded(d.path()))
+                    .filter(|d| keep_entry(self.filter_entry.as_ref(), d))
+                    .collect();
+
+                let files: Vec<FileEntry> = files
+                    .into_iter()
+                    .filter(|f| !stack.is_ignored(f.path(), false))
+                    .filter(|f| !self.is_excluded(f.path()))
+                    .filter(|f| self.has_allowed_extension(f.path()))
+                    .filter(|f| self.is_allowed_glob(f.path()))
+                    .collect();
+
+                let descend: bool = !at_depth_limit(self.max_depth, depth);
+
+                self.dirs_visited += 1;
+                self.files_discovered += files.len();
+
+                let mut emitted: Vec<FileEntry> = files;
+                if self.yield_dirs {
+                    emitted.extend(dirs.iter().cloned());
                 }
-            }
-            Err(e) => log::warn!("{}: {:?}", e, path),
-        }
-    }
+                emitted.retain(|e| at_min_depth(self.min_depth, e.depth()));
+                if let Some(cmp) = &self.sort_by {
+                    emitted.sort_by(|a, b| cmp(a, b));
+                }
+                self.files.extend(emitted);
 
-    fn at_max_depth(&self, path: &PathBuf) -> bool {
-        match self.max_depth {
-            Some(max_depth) => {
-                let current_depth: u32 = self.depth(path) as u32;
-                current_depth >= max_depth
+                if descend {
+                    let mut dirs: Vec<FileEntry> = dirs;
+                    if let Some(cmp) = &self.sort_by {
+                        dirs.sort_by(|a, b| cmp(a, b));
+                    }
+                    self.dirs.extend(
+                        dirs.into_iter()
+                            .map(|d| (d.into_path(), depth + 1, stack.clone())),
+                    );
+                }
+                self.report(path);
+            }
+            Err(e) => {
+                self.errors += 1;
+                log::warn!("{}: {:?}", e, path);
+                self.report(path);
             }
-            None => false,
         }
     }
 
-    fn depth(&self, dir: &PathBuf) -> usize {
-        components(dir) - self.origin_depth
+}
+
+fn read_entries(
+    path: &PathBuf,
+    depth: u32,
+    follow_symlinks: bool,
+) -> Result<(Vec<FileEntry>, Vec<FileEntry>), std::io::Error> {
+    let read_dir: ReadDir = read_dirs(path)?;
+    let (files, dirs): (Vec<RawEntry>, Vec<RawEntry>) = read_dir
+        .filter_map(|p| p.ok())
+        .filter(|d: &DirEntry| is_valid_target(d, follow_symlinks))
+        .filter_map(|d: DirEntry| d.file_type().ok().map(|ft| (d.path(), ft)))
+        .partition(|(p, _)| p.is_file());
+
+    let files: Vec<FileEntry> = files
+        .into_iter()
+        .map(|(p, ft)| FileEntry::new(p, ft, depth))
+        .collect();
+    let dirs: Vec<FileEntry> = dirs
+        .into_iter()
+        .map(|(p, ft)| FileEntry::new(p, ft, depth))
+        .collect();
+
+    Ok((files, dirs))
+}
+
+fn at_depth_limit(max_depth: Option<u32>, depth: u32) -> bool {
+    match max_depth {
+        Some(max_depth) => depth >= max_depth,
+        None => false,
     }
 }
 
-fn components(path: &PathBuf) -> usize {
-    path.canonicalize()
-        .expect("Unable to canonicalize path")
-        .components()
-        .count()
+fn at_min_depth(min_depth: Option<u32>, depth: u32) -> bool {
+    match min_depth {
+        Some(min_depth) => depth >= min_depth,
+        None => true,
+    }
 }
 
-fn filter_boundaries(dirs: Vec<PathBuf>, boundaries: &[PathBuf]) -> Vec<PathBuf> {
-    dirs.iter()
-        .filter(|d| !boundaries.contains(d))
-        .map(|d| d.to_path_buf())
-        .collect()
+fn keep_entry(filter: Option<&EntryFilter>, entry: &FileEntry) -> bool {
+    match filter {
+        Some(pred) => pred(entry),
+        None => true,
+    }
 }
 
 impl Iterator for Walker {
-    type Item = PathBuf;
+    type Item = FileEntry;
     fn next(&mut self) -> Option<Self::Item> {
         loop {
+            if self.is_cancelled() {
+                return None;
+            }
             match self.files.pop_front() {
                 Some(f) => break Some(f),
                 None => match self.dirs.pop_front() {
-                    Some(d) => {
-                        self.push(&d);
+                    Some((d, depth, stack)) => {
+                        self.push(&d, depth, stack);
                         continue;
                     }
                     None => break None,
@@ -225,8 +930,7 @@ impl Iterator for Walker {
 }
 
 fn read_dirs(path: &PathBuf) -> Result<ReadDir, std::io::Error> {
-    let full_path: PathBuf = path.canonicalize()?;
-    Ok(std::fs::read_dir(full_path)?)
+    std::fs::read_dir(path)
 }
 
 #[inline]
@@ -244,8 +948,8 @@ impl std::fmt::Display for Walker {
             f,
             "origin: {:?}, current file: {:?}, current directory: {:?}",
             &self.origin,
-            self.files.get(0),
-            self.dirs.get(0)
+            self.files.front().map(FileEntry::path),
+            self.dirs.front().map(|(d, _, _)| d)
         )
     }
 }
@@ -258,19 +962,20 @@ impl Default for Walker {
 
 impl std::cmp::Ord for Walker {
     fn cmp(&self, other: &Self) -> Ordering {
-        let left: usize = current_depth(self);
-        let right: usize = current_depth(other);
+        let left: u32 = current_depth(self);
+        let right: u32 = current_depth(other);
         right.cmp(&left)
     }
 }
 
-fn current_depth(walker: &Walker) -> usize {
-    let fallback: PathBuf = PathBuf::new();
-    let path: &PathBuf = walker
-        .files
-        .get(0)
-        .unwrap_or_else(|| walker.dirs.get(0).unwrap_or(&fallback));
-    components(path)
+fn current_depth(walker: &Walker) -> u32 {
+    if let Some(file) = walker.files.front() {
+        return file.depth();
+    }
+    match walker.dirs.front() {
+        Some((_, depth, _)) => *depth,
+        None => 0,
+    }
 }
 
 impl std::cmp::PartialOrd for Walker {
@@ -324,9 +1029,9 @@ mod tests {
     #[test]
     fn test_reset() {
         let mut walker = Walker::from(TEST_DIR).unwrap();
-        let file0: PathBuf = walker.next().unwrap();
+        let file0: PathBuf = walker.next().unwrap().into_path();
         walker.reset();
-        let file1: PathBuf = walker.next().unwrap();
+        let file1: PathBuf = walker.next().unwrap().into_path();
         assert_eq!(file0, file1);
     }
 
@@ -378,6 +1083,133 @@ mod tests {
         assert_ne!(walker0, walker1)
     }
 
+    #[test]
+    fn test_walk_parallel_finds_all_files() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let files = AtomicUsize::new(0);
+        Walker::from(TEST_DIR).unwrap().walk_parallel(4, |entry| {
+            if entry.is_file() {
+                files.fetch_add(1, Ordering::SeqCst);
+            }
+            crate::WalkState::Continue
+        });
+        assert_eq!(5, files.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_walk_parallel_skip_dir_prunes_subtree() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        let files = AtomicUsize::new(0);
+        Walker::from(TEST_DIR).unwrap().walk_parallel(2, |entry| {
+            if entry.is_dir() {
+                return crate::WalkState::SkipDir;
+            }
+            files.fetch_add(1, Ordering::SeqCst);
+            crate::WalkState::Continue
+        });
+        assert_eq!(1, files.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn test_yield_dirs() {
+        let found = Walker::from(TEST_DIR).unwrap().yield_dirs().count();
+        assert_eq!(8, found);
+    }
+
+    #[test]
+    fn test_min_depth_excludes_shallow_files() {
+        let found = Walker::from(TEST_DIR).unwrap().min_depth(2).count();
+        assert_eq!(4, found);
+    }
+
+    #[test]
+    fn test_filter_entry_prunes_subtree() {
+        let found = Walker::from(TEST_DIR)
+            .unwrap()
+            .filter_entry(|entry| {
+                !entry
+                    .path()
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .map(|n| n.starts_with('.'))
+                    .unwrap_or(false)
+            })
+            .count();
+        assert_eq!(4, found);
+    }
+
+    #[test]
+    fn test_sort_by_file_name() {
+        let other_dir: String = format!("{}/dir0", TEST_DIR);
+        let names: Vec<String> = Walker::from(other_dir)
+            .unwrap()
+            .sort_by_file_name()
+            .take(3)
+            .map(|e| e.path().file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        assert_eq!(vec![".hidden_file", "file1", "file2"], names);
+    }
+
+    #[test]
+    fn test_with_extensions_filters_files() {
+        let found = Walker::from(TEST_DIR).unwrap().with_extensions(&["rs"]).count();
+        assert_eq!(0, found);
+    }
+
+    #[test]
+    fn test_exclude_globs_prunes_directory() {
+        let found = Walker::from(TEST_DIR)
+            .unwrap()
+            .exclude_globs(&["**/dir0"])
+            .count();
+        assert_eq!(1, found);
+    }
+
+    #[test]
+    fn test_allow_globs_filters_emitted_files() {
+        let found = Walker::from(TEST_DIR)
+            .unwrap()
+            .allow_globs(&["**/file1"])
+            .count();
+        assert_eq!(1, found);
+    }
+
+    #[test]
+    fn test_progress_reports_totals() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let count = Walker::from(TEST_DIR).unwrap().with_progress(tx).count();
+        assert_eq!(5, count);
+        let events: Vec<crate::WalkProgress> = rx.iter().collect();
+        let last = events.last().unwrap();
+        assert_eq!(4, last.directories_visited);
+        assert_eq!(5, last.files_discovered);
+    }
+
+    #[test]
+    fn test_cancel_stops_iteration() {
+        use std::sync::atomic::{AtomicBool, Ordering};
+        use std::sync::Arc;
+        let cancel = Arc::new(AtomicBool::new(true));
+        let count = Walker::from(TEST_DIR).unwrap().with_cancel(cancel).count();
+        assert_eq!(0, count);
+        let cancel = Arc::new(AtomicBool::new(false));
+        let mut walker = Walker::from(TEST_DIR)
+            .unwrap()
+            .with_cancel(Arc::clone(&cancel));
+        assert!(walker.next().is_some());
+        cancel.store(true, Ordering::SeqCst);
+        assert!(walker.next().is_none());
+    }
+
+    #[test]
+    fn test_metadata_is_cached() {
+        let mut walker = Walker::from(TEST_DIR).unwrap();
+        let entry = walker.next().unwrap();
+        let first = entry.metadata().unwrap() as *const _;
+        let second = entry.metadata().unwrap() as *const _;
+        assert_eq!(first, second);
+    }
+
     #[test]
     fn test_default() {
         let walker0: Walker = Walker::new().unwrap();
@@ -407,7 +1239,7 @@ mod tests {
     fn test_ordering_equal() {
         let walker0 = Walker::from(TEST_DIR).unwrap();
         let walker1 = Walker::from(TEST_DIR).unwrap();
-        assert_eq!(walker0.cmp(walker1), Ordering::Equal)
+        assert_eq!(Ord::cmp(&walker0, &walker1), Ordering::Equal)
     }
 
     #[test]